@@ -0,0 +1,45 @@
+use lex::{expect, skip_space, Lex, LexErrorKind, LexResult};
+use std::ops::Deref;
+use strict_partial_ord::StrictPartialOrd;
+
+/// An owned byte string, the RHS representation for `Type::Bytes` fields
+/// (the LHS representation is a borrowed `&[u8]`, since execution-context
+/// values are read straight out of the caller's buffer). Filter literals
+/// are always written as quoted strings (`str1 == "abc"`), but the value
+/// itself carries no assumption that it's valid UTF-8, since it may come
+/// from raw packet/event data.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+pub struct Bytes(Vec<u8>);
+
+impl<'a> From<&'a str> for Bytes {
+    fn from(s: &'a str) -> Self {
+        Bytes(s.as_bytes().to_vec())
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl StrictPartialOrd<Bytes> for Bytes {}
+
+impl StrictPartialOrd<[u8]> for [u8] {}
+
+impl<'i> Lex<'i> for Bytes {
+    fn lex(input: &'i str) -> LexResult<'i, Self> {
+        let input = skip_space(input);
+        let input = expect(input, "\"")?;
+
+        let end = input
+            .find('"')
+            .ok_or((LexErrorKind::ExpectedLiteral("\""), input))?;
+
+        let (bytes, rest) = input.split_at(end);
+
+        Ok((Bytes::from(bytes), &rest[1..]))
+    }
+}
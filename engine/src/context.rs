@@ -0,0 +1,251 @@
+use filter::{Field, Filter, FilterOp};
+use lex::{expect, skip_space, LexErrorKind};
+use op::{CombiningOp, OrderingOp};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use types::{LhsValue, RhsValue, RhsValues, Type};
+
+/// A named, typed bag of fields. Instantiated two ways in this crate:
+/// `Context<String, Type>` is a `Scheme` (the field/type registry a filter
+/// is parsed and validated against), and `Context<&str, LhsValue>` is the
+/// `ExecutionContext` a parsed filter is matched against.
+pub struct Context<K, V> {
+    map: HashMap<K, V>,
+}
+
+// Written by hand instead of `#[derive(Default)]`, which would add a
+// `K: Default, V: Default` bound neither `HashMap` nor this type actually
+// needs (and that `LhsValue`, used as `V` for an `ExecutionContext`,
+// doesn't satisfy).
+impl<K, V> Default for Context<K, V> {
+    fn default() -> Self {
+        Context {
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl Context<String, Type> {
+    pub fn insert(&mut self, name: String, ty: Type) {
+        self.map.insert(name, ty);
+    }
+
+    pub fn get_key_value<Q: ?Sized + Hash + Eq>(&self, name: &Q) -> Option<(&str, &Type)>
+    where
+        String: Borrow<Q>,
+    {
+        self.map
+            .get_key_value(name)
+            .map(|(name, ty)| (name.as_str(), ty))
+    }
+
+    /// Parses `input` against this scheme, validating each field name and
+    /// RHS literal type as it goes (rather than building an untyped AST
+    /// and checking it afterwards).
+    pub fn parse<'s, 'i>(&'s self, input: &'i str) -> Result<Filter<'s>, (LexErrorKind, &'i str)> {
+        let (filter, rest) = self.parse_or(input)?;
+        let rest = skip_space(rest);
+
+        if !rest.is_empty() {
+            return Err((LexErrorKind::ExpectedName("end of input"), rest));
+        }
+
+        Ok(filter)
+    }
+
+    fn parse_or<'s, 'i>(
+        &'s self,
+        input: &'i str,
+    ) -> Result<(Filter<'s>, &'i str), (LexErrorKind, &'i str)> {
+        self.parse_combined(input, "||", CombiningOp::Or, |ctx, input| {
+            ctx.parse_and(input)
+        })
+    }
+
+    fn parse_and<'s, 'i>(
+        &'s self,
+        input: &'i str,
+    ) -> Result<(Filter<'s>, &'i str), (LexErrorKind, &'i str)> {
+        self.parse_combined(input, "&&", CombiningOp::And, |ctx, input| {
+            ctx.parse_unary(input)
+        })
+    }
+
+    fn parse_combined<'s, 'i, F>(
+        &'s self,
+        input: &'i str,
+        token: &'static str,
+        op: CombiningOp,
+        mut parse_operand: F,
+    ) -> Result<(Filter<'s>, &'i str), (LexErrorKind, &'i str)>
+    where
+        F: FnMut(&'s Self, &'i str) -> Result<(Filter<'s>, &'i str), (LexErrorKind, &'i str)>,
+    {
+        let (first, mut input) = parse_operand(self, input)?;
+        let mut filters = vec![first];
+
+        loop {
+            let trimmed = skip_space(input);
+
+            match expect(trimmed, token) {
+                Ok(rest) => {
+                    let (next, rest) = parse_operand(self, skip_space(rest))?;
+                    filters.push(next);
+                    input = rest;
+                }
+                Err(_) => break,
+            }
+        }
+
+        if filters.len() == 1 {
+            Ok((filters.pop().unwrap(), input))
+        } else {
+            Ok((Filter::Combine(op, filters), input))
+        }
+    }
+
+    fn parse_unary<'s, 'i>(
+        &'s self,
+        input: &'i str,
+    ) -> Result<(Filter<'s>, &'i str), (LexErrorKind, &'i str)> {
+        let input = skip_space(input);
+
+        if let Ok(rest) = expect(input, "(") {
+            let (filter, rest) = self.parse_or(skip_space(rest))?;
+            let rest = expect(skip_space(rest), ")")?;
+            return Ok((filter, rest));
+        }
+
+        self.parse_comparison(input)
+    }
+
+    fn parse_comparison<'s, 'i>(
+        &'s self,
+        input: &'i str,
+    ) -> Result<(Filter<'s>, &'i str), (LexErrorKind, &'i str)> {
+        let input = skip_space(input);
+
+        let name_len = input
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or_else(|| input.len());
+
+        if name_len == 0 {
+            return Err((LexErrorKind::ExpectedName("field name"), input));
+        }
+
+        let (name, rest) = input.split_at(name_len);
+
+        let (field_name, ty) = self
+            .get_key_value(name)
+            .ok_or((LexErrorKind::UnknownField, name))?;
+
+        let trimmed = skip_space(rest);
+
+        if let Ok(rest) = expect(trimmed, "in") {
+            let (set, rest) = RhsValues::lex_for(*ty, skip_space(rest))?;
+
+            return Ok((Filter::Op(Field::new(field_name), FilterOp::In(set)), rest));
+        }
+
+        // `Bool` fields have no RHS literal syntax (`UninhabitedBool::lex` is
+        // unreachable), so they must never reach `RhsValue::lex_with` below;
+        // a bare field name is the only valid comparison against one.
+        if *ty == Type::Bool {
+            return Ok((Filter::Op(Field::new(field_name), FilterOp::IsTrue), rest));
+        }
+
+        const ORDERING_OPS: &[(&str, OrderingOp)] = &[
+            (">=", OrderingOp::GreaterThanOrEqual),
+            ("<=", OrderingOp::LessThanOrEqual),
+            ("==", OrderingOp::Equal),
+            ("!=", OrderingOp::NotEqual),
+            (">", OrderingOp::GreaterThan),
+            ("<", OrderingOp::LessThan),
+            ("~", OrderingOp::Matches),
+        ];
+
+        for &(token, op) in ORDERING_OPS {
+            if let Ok(rest) = expect(trimmed, token) {
+                let (rhs, rest) = RhsValue::lex_with(*ty, skip_space(rest))?;
+
+                return Ok((
+                    Filter::Op(Field::new(field_name), FilterOp::Ordering(op, rhs)),
+                    rest,
+                ));
+            }
+        }
+
+        Err((LexErrorKind::ExpectedName("comparison operator"), trimmed))
+    }
+}
+
+impl<'a> Context<&'a str, LhsValue<'a>> {
+    pub fn insert(&mut self, name: &'a str, value: LhsValue<'a>) {
+        if let Some(existing) = self.map.get(name) {
+            let existing_ty = existing.get_type();
+            let new_ty = value.get_type();
+
+            if existing_ty != new_ty {
+                panic!(
+                    "Field {} was previously registered with type {:?} but now contains {:?}",
+                    name, existing_ty, new_ty
+                );
+            }
+        }
+
+        self.map.insert(name, value);
+    }
+
+    fn get(&self, name: &str) -> &LhsValue<'a> {
+        self.map
+            .get(name)
+            .unwrap_or_else(|| panic!("Could not find previously registered field {}", name))
+    }
+
+    pub fn execute(&self, filter: &Filter) -> bool {
+        match *filter {
+            Filter::Combine(CombiningOp::And, ref filters) => {
+                filters.iter().all(|filter| self.execute(filter))
+            }
+            Filter::Combine(CombiningOp::Or, ref filters) => {
+                filters.iter().any(|filter| self.execute(filter))
+            }
+            Filter::Op(ref field, ref op) => op.matches(self.get(field.name())),
+        }
+    }
+
+    /// Evaluates every filter in `filters` against this context, resolving
+    /// each field it needs at most once and sharing that resolution across
+    /// every filter in the batch, rather than calling `execute` (which
+    /// re-resolves every field) once per filter.
+    pub fn execute_all(&self, filters: &[&Filter]) -> Vec<bool> {
+        let mut cache: HashMap<&str, &LhsValue<'a>> = HashMap::new();
+
+        filters
+            .iter()
+            .map(|filter| self.execute_cached(filter, &mut cache))
+            .collect()
+    }
+
+    fn execute_cached<'b>(
+        &'b self,
+        filter: &Filter<'b>,
+        cache: &mut HashMap<&'b str, &'b LhsValue<'a>>,
+    ) -> bool {
+        match *filter {
+            Filter::Combine(CombiningOp::And, ref filters) => filters
+                .iter()
+                .all(|filter| self.execute_cached(filter, cache)),
+            Filter::Combine(CombiningOp::Or, ref filters) => filters
+                .iter()
+                .any(|filter| self.execute_cached(filter, cache)),
+            Filter::Op(ref field, ref op) => {
+                let name = field.name();
+                let value = *cache.entry(name).or_insert_with(|| self.get(name));
+
+                op.matches(value)
+            }
+        }
+    }
+}
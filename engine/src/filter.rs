@@ -0,0 +1,98 @@
+use bytes::Bytes;
+use op::{CombiningOp, OrderingOp};
+use regex::bytes::Regex;
+use types::{LhsValue, RhsValue, RhsValues, Type};
+
+/// A field name, interned from the `Scheme` it was looked up against (or,
+/// for a deserialized filter, re-looked-up and validated against one).
+///
+/// Only `Serialize`, not `Deserialize`: deserializing a `Filter` always goes
+/// through `SerializedFilter`, which holds field names as owned `String`s and
+/// re-resolves them against a `Scheme` via `validate`, so a `Field<'a>` is
+/// never deserialized directly (and, borrowing `&'a str`, can't satisfy the
+/// `'de: 'a` serde would require if it tried).
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub struct Field<'a>(&'a str);
+
+impl<'a> Field<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Field(name)
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.0
+    }
+}
+
+/// The operator half of `field <op>`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum FilterOp {
+    Ordering(OrderingOp, RhsValue),
+    In(RhsValues),
+    IsTrue,
+}
+
+impl FilterOp {
+    /// The type a field must have for this op to apply to it; `None` for
+    /// `IsTrue`, which only ever applies to `Type::Bool`.
+    pub fn rhs_type(&self) -> Option<Type> {
+        match *self {
+            FilterOp::Ordering(_, ref rhs) => Some(rhs.get_type()),
+            FilterOp::In(ref set) => Some(set.ty()),
+            FilterOp::IsTrue => None,
+        }
+    }
+
+    pub fn matches(&self, lhs: &LhsValue) -> bool {
+        match (self, lhs) {
+            (&FilterOp::IsTrue, &LhsValue::Bool(b)) => b,
+            (
+                &FilterOp::Ordering(OrderingOp::Matches, RhsValue::Bytes(ref pattern)),
+                &LhsValue::Bytes(text),
+            ) => matches_regex(pattern, text),
+            // `~` against any non-`Bytes` pair never matches.
+            (&FilterOp::Ordering(OrderingOp::Matches, _), _) => false,
+            (&FilterOp::Ordering(op, ref rhs), lhs) => matches_ordering(op, lhs, rhs),
+            (&FilterOp::In(ref set), lhs) => set.matches(lhs),
+            _ => false,
+        }
+    }
+}
+
+fn matches_regex(pattern: &Bytes, text: &[u8]) -> bool {
+    let pattern = String::from_utf8_lossy(pattern);
+
+    Regex::new(&pattern)
+        .map(|re| re.is_match(text))
+        .unwrap_or(false)
+}
+
+fn matches_ordering(op: OrderingOp, lhs: &LhsValue, rhs: &RhsValue) -> bool {
+    match (lhs, rhs) {
+        (&LhsValue::Ip(ref l), &RhsValue::Ip(ref r)) => op.matches_ord(l, r),
+        (&LhsValue::Bytes(l), &RhsValue::Bytes(ref r)) => op.matches_ord(l, &**r),
+        (&LhsValue::Unsigned(ref l), &RhsValue::Unsigned(ref r)) => op.matches_ord(l, r),
+        // `strict_partial_cmp` panics on an incomparable pair, and a `NaN`
+        // LHS (which, unlike the RHS `Float` literal, isn't rejected by a
+        // `Lex` impl — it can only arrive via the FFI insertion point) is
+        // exactly that, so it's treated as a non-match instead.
+        (&LhsValue::Float(ref l), &RhsValue::Float(ref r)) => {
+            !l.is_nan() && op.matches_ord(l, r)
+        }
+        (&LhsValue::Timestamp(ref l), &RhsValue::Timestamp(ref r)) => op.matches_ord(l, r),
+        (&LhsValue::Int(ref l), &RhsValue::Int(ref r)) => op.matches_ord(l, r),
+        _ => false,
+    }
+}
+
+/// The filter AST: either a leaf comparison against a single field, or a
+/// list of sub-filters combined with `&&`/`||`.
+///
+/// Only `Serialize`, not `Deserialize`, for the same reason as `Field`: the
+/// FFI boundary deserializes a `SerializedFilter` and calls `validate` to
+/// produce one of these, never deserializes a `Filter` itself.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum Filter<'a> {
+    Op(Field<'a>, FilterOp),
+    Combine(CombiningOp, Vec<Filter<'a>>),
+}
@@ -0,0 +1,51 @@
+use std::fmt::{self, Display};
+use std::num::{ParseFloatError, ParseIntError};
+
+/// The result of lexing a single token: the parsed value and whatever
+/// input is left, or an error paired with the span that caused it.
+pub type LexResult<'i, T> = Result<(T, &'i str), (LexErrorKind, &'i str)>;
+
+/// A type that can consume a prefix of its textual representation off the
+/// front of a filter (or a JSON-free RHS literal) and return what's left.
+pub trait Lex<'i>: Sized {
+    fn lex(input: &'i str) -> LexResult<'i, Self>;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LexErrorKind {
+    ExpectedName(&'static str),
+    ExpectedLiteral(&'static str),
+    UnknownField,
+    TypeMismatch,
+    ParseInt(ParseIntError),
+    ParseFloat(ParseFloatError),
+}
+
+impl Display for LexErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LexErrorKind::ExpectedName(name) => write!(f, "expected {}", name),
+            LexErrorKind::ExpectedLiteral(lit) => write!(f, "expected {:?}", lit),
+            LexErrorKind::UnknownField => write!(f, "unknown field"),
+            LexErrorKind::TypeMismatch => write!(f, "type mismatch"),
+            LexErrorKind::ParseInt(ref err) => write!(f, "{}", err),
+            LexErrorKind::ParseFloat(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Trims leading `' '` characters, which the grammar allows everywhere a
+/// token boundary is expected; tabs and newlines are not skipped.
+pub fn skip_space(input: &str) -> &str {
+    input.trim_start_matches(' ')
+}
+
+/// Consumes `literal` off the front of `input`, or fails without consuming
+/// anything.
+pub fn expect<'i>(input: &'i str, literal: &'static str) -> Result<&'i str, (LexErrorKind, &'i str)> {
+    if input.starts_with(literal) {
+        Ok(&input[literal.len()..])
+    } else {
+        Err((LexErrorKind::ExpectedLiteral(literal), input))
+    }
+}
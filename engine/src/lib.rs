@@ -0,0 +1,17 @@
+extern crate regex;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+
+mod bytes;
+mod context;
+mod filter;
+pub mod lex;
+pub mod op;
+pub mod rhs_types;
+pub mod strict_partial_ord;
+pub mod types;
+
+pub use bytes::Bytes;
+pub use context::Context;
+pub use filter::{Field, Filter, FilterOp};
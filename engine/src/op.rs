@@ -0,0 +1,42 @@
+use std::cmp::Ordering;
+use strict_partial_ord::StrictPartialOrd;
+
+/// How multiple filters are combined with `&&` / `||`.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum CombiningOp {
+    And,
+    Or,
+}
+
+/// A binary comparison between a field and an RHS literal.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum OrderingOp {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    Matches,
+}
+
+impl OrderingOp {
+    /// Evaluates every op except `Matches`, which only makes sense for
+    /// `Bytes` and is handled by the caller via regex instead.
+    pub fn matches_ord<L: ?Sized, R: ?Sized>(self, lhs: &L, rhs: &R) -> bool
+    where
+        L: StrictPartialOrd<R>,
+    {
+        let ord = lhs.strict_partial_cmp(rhs);
+
+        match self {
+            OrderingOp::Equal => ord == Ordering::Equal,
+            OrderingOp::NotEqual => ord != Ordering::Equal,
+            OrderingOp::GreaterThan => ord == Ordering::Greater,
+            OrderingOp::GreaterThanOrEqual => ord != Ordering::Less,
+            OrderingOp::LessThan => ord == Ordering::Less,
+            OrderingOp::LessThanOrEqual => ord != Ordering::Greater,
+            OrderingOp::Matches => unreachable!("Matches is handled via regex, not ordering"),
+        }
+    }
+}
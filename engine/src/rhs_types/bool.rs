@@ -4,7 +4,7 @@ use strict_partial_ord::StrictPartialOrd;
 
 /// Uninhabited / empty type for `bool` with traits we need for RHS values.
 /// See https://doc.rust-lang.org/nomicon/exotic-sizes.html#empty-types.
-#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub enum UninhabitedBool {}
 
 impl Borrow<bool> for UninhabitedBool {
@@ -0,0 +1,64 @@
+use lex::{skip_space, Lex, LexErrorKind, LexResult};
+use std::cmp::Ordering;
+use std::ops::Deref;
+use strict_partial_ord::StrictPartialOrd;
+
+/// A 64-bit floating point RHS value. `Lex` only accepts finite decimal
+/// literals, so this never holds a `NaN`.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Serialize, Deserialize)]
+pub struct Float(f64);
+
+impl From<f64> for Float {
+    fn from(n: f64) -> Self {
+        Float(n)
+    }
+}
+
+impl Deref for Float {
+    type Target = f64;
+
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+impl PartialEq<Float> for f64 {
+    fn eq(&self, other: &Float) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialOrd<Float> for f64 {
+    fn partial_cmp(&self, other: &Float) -> Option<Ordering> {
+        self.partial_cmp(&other.0)
+    }
+}
+
+impl StrictPartialOrd<Float> for f64 {}
+
+impl<'i> Lex<'i> for Float {
+    fn lex(input: &'i str) -> LexResult<'i, Self> {
+        let input = skip_space(input);
+
+        let sign_len = if input.starts_with('-') || input.starts_with('+') {
+            1
+        } else {
+            0
+        };
+
+        let digits_len = input[sign_len..]
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .map_or(input.len() - sign_len, |pos| pos);
+
+        if digits_len == 0 {
+            return Err((LexErrorKind::ExpectedName("float number"), input));
+        }
+
+        let (digits, rest) = input.split_at(sign_len + digits_len);
+
+        match digits.parse() {
+            Ok(n) => Ok((Float(n), rest)),
+            Err(err) => Err((LexErrorKind::ParseFloat(err), digits)),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use lex::{skip_space, Lex, LexErrorKind, LexResult};
+use std::cmp::Ordering;
+use std::ops::Deref;
+use strict_partial_ord::StrictPartialOrd;
+
+/// A signed 64-bit integer RHS value, for fields that can go negative.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Serialize, Deserialize)]
+pub struct Int(i64);
+
+impl From<i64> for Int {
+    fn from(n: i64) -> Self {
+        Int(n)
+    }
+}
+
+impl Deref for Int {
+    type Target = i64;
+
+    fn deref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl PartialEq<Int> for i64 {
+    fn eq(&self, other: &Int) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialOrd<Int> for i64 {
+    fn partial_cmp(&self, other: &Int) -> Option<Ordering> {
+        self.partial_cmp(&other.0)
+    }
+}
+
+impl StrictPartialOrd<Int> for i64 {}
+
+impl<'i> Lex<'i> for Int {
+    fn lex(input: &'i str) -> LexResult<'i, Self> {
+        let input = skip_space(input);
+
+        let digits_input = if input.starts_with('-') || input.starts_with('+') {
+            &input[1..]
+        } else {
+            input
+        };
+
+        let digits_len = digits_input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or_else(|| digits_input.len());
+
+        if digits_len == 0 {
+            return Err((LexErrorKind::ExpectedName("digit"), digits_input));
+        }
+
+        let len = (input.len() - digits_input.len()) + digits_len;
+        let (digits, rest) = input.split_at(len);
+
+        match digits.parse() {
+            Ok(n) => Ok((Int(n), rest)),
+            Err(err) => Err((LexErrorKind::ParseInt(err), digits)),
+        }
+    }
+}
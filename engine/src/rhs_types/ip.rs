@@ -0,0 +1,23 @@
+use lex::{skip_space, Lex, LexErrorKind, LexResult};
+use std::net::IpAddr;
+use std::str::FromStr;
+use strict_partial_ord::StrictPartialOrd;
+
+impl StrictPartialOrd<IpAddr> for IpAddr {}
+
+impl<'i> Lex<'i> for IpAddr {
+    fn lex(input: &'i str) -> LexResult<'i, Self> {
+        let input = skip_space(input);
+
+        let len = input
+            .find(|c: char| !(c.is_ascii_hexdigit() || c == '.' || c == ':'))
+            .unwrap_or_else(|| input.len());
+
+        let (candidate, rest) = input.split_at(len);
+
+        match IpAddr::from_str(candidate) {
+            Ok(addr) => Ok((addr, rest)),
+            Err(_) => Err((LexErrorKind::ExpectedName("IP address"), candidate)),
+        }
+    }
+}
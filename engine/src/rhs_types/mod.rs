@@ -0,0 +1,11 @@
+mod bool;
+mod float;
+mod int;
+mod ip;
+mod timestamp;
+mod unsigned;
+
+pub use self::bool::UninhabitedBool;
+pub use self::float::Float;
+pub use self::int::Int;
+pub use self::timestamp::Timestamp;
@@ -0,0 +1,58 @@
+use lex::{skip_space, Lex, LexErrorKind, LexResult};
+use std::cmp::Ordering;
+use std::ops::Deref;
+use strict_partial_ord::StrictPartialOrd;
+
+/// A point in time, stored as a unix epoch in seconds so ordering falls
+/// straight through to the same machinery as `Unsigned`.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Serialize, Deserialize)]
+pub struct Timestamp(u64);
+
+impl From<u64> for Timestamp {
+    fn from(epoch_secs: u64) -> Self {
+        Timestamp(epoch_secs)
+    }
+}
+
+impl Deref for Timestamp {
+    type Target = u64;
+
+    fn deref(&self) -> &u64 {
+        &self.0
+    }
+}
+
+impl PartialEq<Timestamp> for u64 {
+    fn eq(&self, other: &Timestamp) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialOrd<Timestamp> for u64 {
+    fn partial_cmp(&self, other: &Timestamp) -> Option<Ordering> {
+        self.partial_cmp(&other.0)
+    }
+}
+
+impl StrictPartialOrd<Timestamp> for u64 {}
+
+impl<'i> Lex<'i> for Timestamp {
+    fn lex(input: &'i str) -> LexResult<'i, Self> {
+        let input = skip_space(input);
+
+        let len = input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or_else(|| input.len());
+
+        if len == 0 {
+            return Err((LexErrorKind::ExpectedName("digit"), input));
+        }
+
+        let (digits, rest) = input.split_at(len);
+
+        match digits.parse() {
+            Ok(epoch_secs) => Ok((Timestamp(epoch_secs), rest)),
+            Err(err) => Err((LexErrorKind::ParseInt(err), digits)),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use lex::{skip_space, Lex, LexErrorKind, LexResult};
+use strict_partial_ord::StrictPartialOrd;
+
+impl StrictPartialOrd<u64> for u64 {}
+
+impl<'i> Lex<'i> for u64 {
+    fn lex(input: &'i str) -> LexResult<'i, Self> {
+        let input = skip_space(input);
+
+        let len = input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or_else(|| input.len());
+
+        if len == 0 {
+            return Err((LexErrorKind::ExpectedName("digit"), input));
+        }
+
+        let (digits, rest) = input.split_at(len);
+
+        match digits.parse() {
+            Ok(n) => Ok((n, rest)),
+            Err(err) => Err((LexErrorKind::ParseInt(err), digits)),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use std::cmp::Ordering;
+
+/// Like `PartialOrd`, but for pairs of types that must always be
+/// comparable in this engine (there's no sensible "doesn't match" result
+/// for an ordering operator). Comparing two values that turn out to be
+/// incomparable is a bug, not a filter that just doesn't match, so it
+/// panics instead of returning `None`.
+pub trait StrictPartialOrd<Rhs: ?Sized = Self>: PartialOrd<Rhs> {
+    fn strict_partial_cmp(&self, other: &Rhs) -> Ordering {
+        self.partial_cmp(other)
+            .expect("values of this type must always be comparable")
+    }
+}
@@ -0,0 +1,153 @@
+use bytes::Bytes;
+use rhs_types::{Float, Int, Timestamp, UninhabitedBool};
+use std::net::IpAddr;
+
+/// Declares `Type`, `LhsValue` and `RhsValue` together from one list of
+/// fields, so adding a field type can't add an `LhsValue`/`RhsValue`
+/// variant while forgetting its `Type` (or vice versa).
+macro_rules! declare_types {
+    ($($name:ident ( $lhs_ty:ty | $rhs_ty:ty ),)*) => {
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+        pub enum Type {
+            $($name,)*
+        }
+
+        #[derive(Debug, PartialEq, Clone, Serialize)]
+        pub enum LhsValue<'a> {
+            $($name($lhs_ty),)*
+        }
+
+        impl<'a> LhsValue<'a> {
+            pub fn get_type(&self) -> Type {
+                match *self {
+                    $(LhsValue::$name(_) => Type::$name,)*
+                }
+            }
+        }
+
+        #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+        pub enum RhsValue {
+            $($name($rhs_ty),)*
+        }
+
+        impl RhsValue {
+            pub fn get_type(&self) -> Type {
+                match *self {
+                    $(RhsValue::$name(_) => Type::$name,)*
+                }
+            }
+
+            pub fn lex_with<'i>(ty: Type, input: &'i str) -> ::lex::LexResult<'i, Self> {
+                use lex::Lex;
+
+                match ty {
+                    $(Type::$name => {
+                        <$rhs_ty>::lex(input).map(|(value, rest)| (RhsValue::$name(value), rest))
+                    })*
+                }
+            }
+        }
+    };
+}
+
+declare_types! {
+    Ip(IpAddr | IpAddr),
+    Bytes(&'a [u8] | Bytes),
+    Unsigned(u64 | u64),
+    Float(f64 | Float),
+    Bool(bool | UninhabitedBool),
+    Timestamp(u64 | Timestamp),
+    Int(i64 | Int),
+}
+
+/// A typed, homogeneous collection of RHS literals, used by the `in`
+/// membership operator (e.g. `ip1 in { 10.0.0.1 192.168.0.0 }`).
+///
+/// One variant per scalar type rather than `Vec<RhsValue>` so a set mixing
+/// element types (`{ 10.0.0.1 443 }`) is rejected by `lex_for` up front,
+/// instead of discovering the mismatch lazily during a containment check.
+/// `Bool` has no variant here: there's no literal syntax for a bare `bool`,
+/// so it can never appear inside a set.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum RhsValues {
+    Ip(Vec<IpAddr>),
+    Bytes(Vec<Bytes>),
+    Unsigned(Vec<u64>),
+    Int(Vec<Int>),
+    Float(Vec<Float>),
+    Timestamp(Vec<Timestamp>),
+}
+
+impl RhsValues {
+    /// The type every element of this set was checked against.
+    pub fn ty(&self) -> Type {
+        match *self {
+            RhsValues::Ip(_) => Type::Ip,
+            RhsValues::Bytes(_) => Type::Bytes,
+            RhsValues::Unsigned(_) => Type::Unsigned,
+            RhsValues::Int(_) => Type::Int,
+            RhsValues::Float(_) => Type::Float,
+            RhsValues::Timestamp(_) => Type::Timestamp,
+        }
+    }
+
+    /// Tests membership of an execution-context value, the same equality
+    /// each type already uses for the `==` ordering operator.
+    pub fn matches(&self, lhs: &LhsValue) -> bool {
+        match (self, lhs) {
+            (&RhsValues::Ip(ref set), &LhsValue::Ip(ref v)) => set.iter().any(|item| item == v),
+            (&RhsValues::Bytes(ref set), &LhsValue::Bytes(v)) => {
+                set.iter().any(|item| &**item == v)
+            }
+            (&RhsValues::Unsigned(ref set), &LhsValue::Unsigned(v)) => {
+                set.iter().any(|item| *item == v)
+            }
+            (&RhsValues::Int(ref set), &LhsValue::Int(v)) => set.iter().any(|item| v == *item),
+            (&RhsValues::Float(ref set), &LhsValue::Float(v)) => {
+                set.iter().any(|item| v == *item)
+            }
+            (&RhsValues::Timestamp(ref set), &LhsValue::Timestamp(v)) => {
+                set.iter().any(|item| v == *item)
+            }
+            _ => false,
+        }
+    }
+
+    /// Lexes a brace-delimited set literal whose elements must all be of
+    /// `ty` (the type of the field on the left of `in`), e.g. for
+    /// `ty == Type::Ip`, `{ 10.0.0.1 192.168.0.0 }`.
+    pub fn lex_for(ty: Type, input: &str) -> ::lex::LexResult<Self> {
+        use lex::{expect, skip_space, Lex, LexErrorKind};
+
+        let input = expect(input, "{")?;
+
+        macro_rules! lex_elements {
+            ($variant:ident, $elem:ty) => {{
+                let mut input = skip_space(input);
+                let mut values = Vec::new();
+
+                loop {
+                    input = skip_space(input);
+
+                    if let Ok(rest) = expect(input, "}") {
+                        return Ok((RhsValues::$variant(values), rest));
+                    }
+
+                    let (value, rest) = <$elem>::lex(input)?;
+                    values.push(value);
+                    input = rest;
+                }
+            }};
+        }
+
+        match ty {
+            Type::Ip => lex_elements!(Ip, IpAddr),
+            Type::Bytes => lex_elements!(Bytes, Bytes),
+            Type::Unsigned => lex_elements!(Unsigned, u64),
+            Type::Int => lex_elements!(Int, Int),
+            Type::Float => lex_elements!(Float, Float),
+            Type::Timestamp => lex_elements!(Timestamp, Timestamp),
+            Type::Bool => Err((LexErrorKind::ExpectedName("settable type"), input)),
+        }
+    }
+}
@@ -1,4 +1,8 @@
 extern crate libc;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
 extern crate wirefilter;
 
 mod strings;
@@ -7,10 +11,12 @@ use libc::size_t;
 use std::cmp::max;
 use std::fmt;
 use std::net::IpAddr;
+use std::slice;
 use std::str::FromStr;
 use strings::{ExternallyAllocatedStr, RustAllocatedString};
-use wirefilter::{Bytes, Context, Filter};
+use wirefilter::{Bytes, Context, Field, Filter, FilterOp};
 use wirefilter::lex::LexErrorKind;
+use wirefilter::op::CombiningOp;
 use wirefilter::types::{LhsValue, Type};
 
 pub struct ParseError<'a> {
@@ -112,6 +118,38 @@ pub extern "C" fn wirefilter_add_bytes_type_field_to_scheme<'a>(
     scheme.insert(name.to_string(), Type::Bytes);
 }
 
+#[no_mangle]
+pub extern "C" fn wirefilter_add_float_type_field_to_scheme<'a>(
+    scheme: &mut Scheme,
+    name: ExternallyAllocatedStr<'a>,
+) {
+    scheme.insert(name.to_string(), Type::Float);
+}
+
+#[no_mangle]
+pub extern "C" fn wirefilter_add_bool_type_field_to_scheme<'a>(
+    scheme: &mut Scheme,
+    name: ExternallyAllocatedStr<'a>,
+) {
+    scheme.insert(name.to_string(), Type::Bool);
+}
+
+#[no_mangle]
+pub extern "C" fn wirefilter_add_timestamp_type_field_to_scheme<'a>(
+    scheme: &mut Scheme,
+    name: ExternallyAllocatedStr<'a>,
+) {
+    scheme.insert(name.to_string(), Type::Timestamp);
+}
+
+#[no_mangle]
+pub extern "C" fn wirefilter_add_int_type_field_to_scheme<'a>(
+    scheme: &mut Scheme,
+    name: ExternallyAllocatedStr<'a>,
+) {
+    scheme.insert(name.to_string(), Type::Int);
+}
+
 #[no_mangle]
 pub extern "C" fn wirefilter_free_parsing_result(result: ParsingResult) {
     drop(result);
@@ -130,6 +168,99 @@ pub extern "C" fn wirefilter_parse_filter<'s, 'i>(
     }
 }
 
+/// Mirrors `Filter` but keeps field names as owned `String`s instead of the
+/// scheme-interned `&str` that `Field` borrows, since a `Filter` deserialized
+/// from JSON has no scheme to borrow from until it's validated below.
+#[derive(Serialize, Deserialize)]
+enum SerializedFilter {
+    Combine(CombiningOp, Vec<SerializedFilter>),
+    Op(String, FilterOp),
+}
+
+impl<'a> From<&'a Filter<'a>> for SerializedFilter {
+    fn from(filter: &'a Filter<'a>) -> Self {
+        match *filter {
+            Filter::Combine(op, ref filters) => {
+                SerializedFilter::Combine(op, filters.iter().map(SerializedFilter::from).collect())
+            }
+            Filter::Op(ref field, ref op) => {
+                SerializedFilter::Op(field.name().to_owned(), op.clone())
+            }
+        }
+    }
+}
+
+impl SerializedFilter {
+    /// Rebuilds a `Filter` against `scheme`, looking up each field name so
+    /// the resulting `Field` borrows the scheme's own copy of the name
+    /// (exactly like a filter that went through `wirefilter_parse_filter`),
+    /// and rejecting anything whose field no longer exists or whose
+    /// operator no longer matches that field's type.
+    fn validate<'s>(self, scheme: &'s Scheme) -> Result<Filter<'s>, String> {
+        match self {
+            SerializedFilter::Combine(op, filters) => {
+                let filters = filters
+                    .into_iter()
+                    .map(|filter| filter.validate(scheme))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(Filter::Combine(op, filters))
+            }
+            SerializedFilter::Op(name, op) => {
+                let (field_name, ty) = scheme
+                    .get_key_value(name.as_str())
+                    .ok_or_else(|| format!("Unknown field `{}`", name))?;
+
+                // `FilterOp::rhs_type` mirrors the check the parser already
+                // does when it type-checks a literal against its field.
+                let compatible = match op.rhs_type() {
+                    Some(rhs_ty) => rhs_ty == *ty,
+                    None => *ty == Type::Bool,
+                };
+
+                if !compatible {
+                    return Err(format!(
+                        "Field `{}` of type {:?} cannot be compared with a {:?}",
+                        name,
+                        ty,
+                        op.rhs_type()
+                    ));
+                }
+
+                Ok(Filter::Op(Field::new(field_name), op))
+            }
+        }
+    }
+}
+
+/// Serializes `filter` to JSON, or an empty string if serialization somehow
+/// fails: this crosses an FFI boundary, so it must not unwind into C by
+/// panicking on a serialization error the way `.expect()` would.
+#[no_mangle]
+pub extern "C" fn wirefilter_serialize_filter(filter: &Filter) -> RustAllocatedString {
+    let serialized = SerializedFilter::from(filter);
+
+    match serde_json::to_string(&serialized) {
+        Ok(json) => RustAllocatedString::from(json),
+        Err(_) => RustAllocatedString::from(String::new()),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn wirefilter_deserialize_filter<'s>(
+    scheme: &'s Scheme,
+    json: ExternallyAllocatedStr,
+) -> ParsingResult<'s> {
+    let result = serde_json::from_str::<SerializedFilter>(json.as_str())
+        .map_err(|err| err.to_string())
+        .and_then(|filter| filter.validate(scheme));
+
+    match result {
+        Ok(filter) => ParsingResult::from(filter),
+        Err(msg) => ParsingResult::Err(RustAllocatedString::from(msg)),
+    }
+}
+
 pub type ExecutionContext<'a> = Context<&'a str, LhsValue<'a>>;
 
 #[no_mangle]
@@ -157,8 +288,7 @@ pub extern "C" fn wirefilter_add_bytes_value_to_execution_context<'a>(
     name: ExternallyAllocatedStr<'a>,
     value: ExternallyAllocatedStr<'a>,
 ) {
-    let bytes = Bytes::from(value.as_str());
-    exec_context.insert(name.as_str(), LhsValue::Bytes(bytes));
+    exec_context.insert(name.as_str(), LhsValue::Bytes(value.as_str().as_bytes()));
 }
 
 #[no_mangle]
@@ -172,11 +302,80 @@ pub extern "C" fn wirefilter_add_ip_value_to_execution_context<'a>(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn wirefilter_add_float_value_to_execution_context<'a>(
+    exec_context: &mut ExecutionContext<'a>,
+    name: ExternallyAllocatedStr<'a>,
+    value: f64,
+) {
+    exec_context.insert(name.as_str(), LhsValue::Float(value));
+}
+
+#[no_mangle]
+pub extern "C" fn wirefilter_add_bool_value_to_execution_context<'a>(
+    exec_context: &mut ExecutionContext<'a>,
+    name: ExternallyAllocatedStr<'a>,
+    value: bool,
+) {
+    exec_context.insert(name.as_str(), LhsValue::Bool(value));
+}
+
+#[no_mangle]
+pub extern "C" fn wirefilter_add_timestamp_value_to_execution_context<'a>(
+    exec_context: &mut ExecutionContext<'a>,
+    name: ExternallyAllocatedStr<'a>,
+    value: u64,
+) {
+    exec_context.insert(name.as_str(), LhsValue::Timestamp(value));
+}
+
+#[no_mangle]
+pub extern "C" fn wirefilter_add_int_value_to_execution_context<'a>(
+    exec_context: &mut ExecutionContext<'a>,
+    name: ExternallyAllocatedStr<'a>,
+    value: i64,
+) {
+    exec_context.insert(name.as_str(), LhsValue::Int(value));
+}
+
 #[no_mangle]
 pub extern "C" fn wirefilter_match(filter: &Filter, exec_context: &ExecutionContext) -> bool {
     exec_context.execute(filter)
 }
 
+/// Evaluates `count` pre-parsed `filters` against a single `exec_context`
+/// in one FFI crossing, which is far cheaper than calling
+/// `wirefilter_match` once per filter when matching one packet/event
+/// against thousands of rules: `Context::execute_all` resolves each field
+/// name it needs at most once across the whole batch and lets every filter
+/// that references it share that resolved `LhsValue`, rather than
+/// re-resolving it from `exec_context` on every filter as a per-filter
+/// `wirefilter_match` loop would.
+///
+/// `exec_context` must outlive every filter in `filters`: this only
+/// evaluates them, it does not take ownership of either.
+///
+/// Returns a caller-owned array of `count` bools that must be freed with
+/// `wirefilter_free_match_results`.
+#[no_mangle]
+pub unsafe extern "C" fn wirefilter_match_all(
+    filters: *const *mut Filter,
+    count: size_t,
+    exec_context: &ExecutionContext,
+) -> *mut bool {
+    let filters = slice::from_raw_parts(filters, count);
+    let filters: Vec<&Filter> = filters.iter().map(|&filter| &*filter).collect();
+
+    let results = exec_context.execute_all(&filters).into_boxed_slice();
+
+    Box::into_raw(results) as *mut bool
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wirefilter_free_match_results(results: *mut bool, count: size_t) {
+    drop(Box::from_raw(slice::from_raw_parts_mut(results, count) as *mut [bool]));
+}
+
 #[cfg(test)]
 mod ffi_test {
     use super::*;
@@ -196,6 +395,14 @@ mod ffi_test {
         wirefilter_add_unsigned_type_field_to_scheme(scheme, ExternallyAllocatedStr::from("num1"));
         wirefilter_add_unsigned_type_field_to_scheme(scheme, ExternallyAllocatedStr::from("num2"));
 
+        wirefilter_add_float_type_field_to_scheme(scheme, ExternallyAllocatedStr::from("float1"));
+        wirefilter_add_bool_type_field_to_scheme(scheme, ExternallyAllocatedStr::from("bool1"));
+        wirefilter_add_timestamp_type_field_to_scheme(
+            scheme,
+            ExternallyAllocatedStr::from("created_at"),
+        );
+        wirefilter_add_int_type_field_to_scheme(scheme, ExternallyAllocatedStr::from("int1"));
+
         test_fn(scheme);
 
         wirefilter_free_scheme(scheme);
@@ -240,6 +447,30 @@ mod ffi_test {
             1337,
         );
 
+        wirefilter_add_float_value_to_execution_context(
+            exec_context,
+            ExternallyAllocatedStr::from("float1"),
+            1.5,
+        );
+
+        wirefilter_add_bool_value_to_execution_context(
+            exec_context,
+            ExternallyAllocatedStr::from("bool1"),
+            true,
+        );
+
+        wirefilter_add_timestamp_value_to_execution_context(
+            exec_context,
+            ExternallyAllocatedStr::from("created_at"),
+            1_609_459_200,
+        );
+
+        wirefilter_add_int_value_to_execution_context(
+            exec_context,
+            ExternallyAllocatedStr::from("int1"),
+            -42,
+        );
+
         exec_context
     }
 
@@ -299,6 +530,42 @@ mod ffi_test {
         });
     }
 
+    #[test]
+    fn serialize_and_deserialize_filter() {
+        test_with_scheme(|scheme| {
+            test_with_filter(r#"num1 > 3 && str2 == "abc""#, |filter| {
+                let json = wirefilter_serialize_filter(filter);
+
+                let result =
+                    wirefilter_deserialize_filter(scheme, ExternallyAllocatedStr::from(json.as_str()));
+
+                match result {
+                    ParsingResult::Ok(deserialized) => {
+                        assert_eq!(*filter, *unsafe { &*deserialized });
+                    }
+                    ParsingResult::Err(ref err) => panic!("{}", err.as_str()),
+                }
+
+                wirefilter_free_parsing_result(result);
+            });
+        });
+    }
+
+    #[test]
+    fn deserialize_filter_rejects_unknown_field() {
+        test_with_scheme(|scheme| {
+            let json = r#"{"Op":["does_not_exist",{"Ordering":["Equal",{"Unsigned":1}]}]}"#;
+            let result = wirefilter_deserialize_filter(scheme, ExternallyAllocatedStr::from(json));
+
+            match result {
+                ParsingResult::Ok(_) => panic!("Error expected"),
+                ParsingResult::Err(_) => {}
+            }
+
+            wirefilter_free_parsing_result(result);
+        });
+    }
+
     #[test]
     fn match_filter() {
         let exec_context = create_execution_context();
@@ -324,6 +591,117 @@ mod ffi_test {
         wirefilter_free_execution_context(exec_context);
     }
 
+    #[test]
+    fn match_new_field_types() {
+        let exec_context = create_execution_context();
+
+        test_with_filter("float1 > 1.0 && created_at > 1609459100", |filter| {
+            assert!(wirefilter_match(filter, exec_context));
+        });
+
+        test_with_filter("bool1", |filter| {
+            assert!(wirefilter_match(filter, exec_context));
+        });
+
+        wirefilter_free_execution_context(exec_context);
+    }
+
+    #[test]
+    fn match_int_field() {
+        let exec_context = create_execution_context();
+
+        test_with_filter("int1 == -42", |filter| {
+            assert!(wirefilter_match(filter, exec_context));
+        });
+
+        test_with_filter("int1 < 0", |filter| {
+            assert!(wirefilter_match(filter, exec_context));
+        });
+
+        wirefilter_free_execution_context(exec_context);
+    }
+
+    #[test]
+    fn match_in_operator() {
+        let exec_context = create_execution_context();
+
+        test_with_filter("ip1 in { 127.0.0.1 10.0.0.1 }", |filter| {
+            assert!(wirefilter_match(filter, exec_context));
+        });
+
+        test_with_filter("ip1 in { 10.0.0.1 192.168.0.0 }", |filter| {
+            assert!(!wirefilter_match(filter, exec_context));
+        });
+
+        wirefilter_free_execution_context(exec_context);
+    }
+
+    #[test]
+    fn parse_error_mixed_types_in_set() {
+        test_with_scheme(|scheme| {
+            let src = "ip1 in { 10.0.0.1 443 }";
+            let result = wirefilter_parse_filter(scheme, ExternallyAllocatedStr::from(src));
+
+            match result {
+                ParsingResult::Ok(_) => panic!("Error expected"),
+                ParsingResult::Err(_) => {}
+            }
+
+            wirefilter_free_parsing_result(result);
+        });
+    }
+
+    #[test]
+    fn parse_error_negative_literal_against_unsigned_field() {
+        test_with_scheme(|scheme| {
+            let src = "num1 == -5";
+            let result = wirefilter_parse_filter(scheme, ExternallyAllocatedStr::from(src));
+
+            match result {
+                ParsingResult::Ok(_) => panic!("Error expected"),
+                ParsingResult::Err(_) => {}
+            }
+
+            wirefilter_free_parsing_result(result);
+        });
+    }
+
+    #[test]
+    fn match_all() {
+        test_with_scheme(|scheme| {
+            let exec_context = create_execution_context();
+
+            let results: Vec<ParsingResult> = [
+                r#"num1 > 41"#,
+                r#"num1 > 100"#,
+                r#"ip1 == 127.0.0.1"#,
+            ].iter()
+                .map(|src| wirefilter_parse_filter(scheme, ExternallyAllocatedStr::from(*src)))
+                .collect();
+
+            let mut filter_ptrs: Vec<*mut Filter> = results
+                .iter()
+                .map(|result| match *result {
+                    ParsingResult::Ok(filter) => filter,
+                    ParsingResult::Err(ref err) => panic!("{}", err.as_str()),
+                })
+                .collect();
+
+            let count = filter_ptrs.len();
+            let matches =
+                unsafe { wirefilter_match_all(filter_ptrs.as_mut_ptr(), count, exec_context) };
+
+            assert_eq!(
+                unsafe { slice::from_raw_parts(matches, count) },
+                [true, false, true]
+            );
+
+            unsafe { wirefilter_free_match_results(matches, count) };
+
+            wirefilter_free_execution_context(exec_context);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Could not find previously registered field num1")]
     fn panic_on_missing_value() {